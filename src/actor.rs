@@ -1,9 +1,61 @@
 use core::any::Any;
 use alloc::sync::Arc;
 
+use core::sync::atomic::{AtomicBool, Ordering};
+
+#[cfg(any(feature = "mailbox", feature = "async"))]
+use core::time::Duration;
+#[cfg(any(feature = "mailbox", feature = "async"))]
+use crate::schedule::TaskHandle;
+
+#[cfg(feature = "async")]
+use alloc::boxed::Box;
+#[cfg(feature = "async")]
+use core::future::Future;
+#[cfg(feature = "async")]
+use core::pin::Pin;
+
 /// # [`Actor`]`
 /// Trait implemented by actors
 pub trait Actor: Send + Sync + 'static {
+    /// How many messages may sit in this actor's mailbox before `send`/`try_send` start applying
+    /// backpressure. Only used when the `mailbox` feature is enabled; actors that need a
+    /// different capacity can override it.
+    #[cfg(feature = "mailbox")]
+    const MAILBOX_CAPACITY: usize = 64;
+
+    /// Called once, by [`Slacktor::spawn`](crate::Slacktor::spawn), right after the actor has
+    /// been installed into the registry. Passed the actor's own `handle`, so the override can do
+    /// setup that needs the actor to already be reachable through it (for example, scheduling a
+    /// recurring message to itself with `send_interval`). No-op by default.
+    #[cfg(feature = "async")]
+    fn started(&self, handle: &ActorHandle<Self>) -> impl core::future::Future<Output = ()> + Send
+    where Self: Sized {
+        let _ = handle;
+        async {}
+    }
+
+    /// Called once, by [`Slacktor::spawn`](crate::Slacktor::spawn), right after the actor has
+    /// been installed into the registry. Passed the actor's own `handle`, so the override can do
+    /// setup that needs the actor to already be reachable through it. No-op by default.
+    #[cfg(not(feature = "async"))]
+    fn started(&self, handle: &ActorHandle<Self>)
+    where Self: Sized {
+        let _ = handle;
+    }
+
+    /// Called once, by `kill`/`shutdown`, before `destroy`. No-op by default; override it to
+    /// begin graceful teardown (stop accepting new work, flush state) distinct from the final
+    /// cleanup in `destroy`, which the same `kill` call runs right after this hook returns.
+    #[cfg(feature = "async")]
+    fn stopping(&self) -> impl core::future::Future<Output = ()> + Send {
+        async {}
+    }
+
+    /// Called once, by `kill`/`shutdown`, before `destroy`. No-op by default.
+    #[cfg(not(feature = "async"))]
+    fn stopping(&self) {}
+
     #[cfg(feature = "async")]
     fn destroy(&self) -> impl core::future::Future<Output = ()> + Send {
         async {}
@@ -45,15 +97,24 @@ pub trait MessageSender<M: Message> {
 }
 
 /// # [`ActorHandle`]
-/// Provides functions to send messages to a given actor.
-#[repr(transparent)]
-pub struct ActorHandle<A: Actor>(Arc<A>);
+/// Provides functions to send messages to a given actor. Also tracks whether the actor is still
+/// alive, so a [`send_later`](ActorHandle::send_later)/[`send_interval`](ActorHandle::send_interval)
+/// delivery scheduled before a kill is skipped instead of reviving a dead actor.
+#[cfg(not(feature = "mailbox"))]
+pub struct ActorHandle<A: Actor> {
+    actor: Arc<A>,
+    alive: Arc<AtomicBool>,
+}
 
+#[cfg(not(feature = "mailbox"))]
 impl<A: Actor> ActorHandle<A> {
     /// # [`ActorHandle::new`]
     /// Creates a new actor handle wrapping the given actor.
     pub(crate) fn new(actor: A) -> Self {
-        Self(Arc::new(actor))
+        Self {
+            actor: Arc::new(actor),
+            alive: Arc::new(AtomicBool::new(true)),
+        }
     }
 
     /// # [`ActorHandle::send`]
@@ -61,7 +122,7 @@ impl<A: Actor> ActorHandle<A> {
     #[cfg(feature = "async")]
     pub async fn send<M: Message>(&self, message: M) -> M::Result
     where A: Handler<M> {
-        self.0.handle_message(message).await
+        self.actor.handle_message(message).await
     }
 
     /// # [`ActorHandle::send`]
@@ -69,35 +130,105 @@ impl<A: Actor> ActorHandle<A> {
     #[cfg(not(feature = "async"))]
     pub fn send<M: Message>(&self, message: M) -> M::Result
     where A: Handler<M> {
-        self.0.handle_message(message)
+        self.actor.handle_message(message)
+    }
+
+
+    #[cfg(feature = "async")]
+    pub(crate) async fn started(&self) {
+        self.actor.started(self).await;
+    }
+
+    #[cfg(not(feature = "async"))]
+    pub(crate) fn started(&self) {
+        self.actor.started(self);
     }
-    
 
     #[cfg(feature = "async")]
     pub async fn kill(&self) {
-        self.0.destroy().await;
+        self.alive.store(false, Ordering::Release);
+        self.actor.stopping().await;
+        self.actor.destroy().await;
     }
 
     #[cfg(not(feature = "async"))]
     pub fn kill(&self) {
-        self.0.destroy();
+        self.alive.store(false, Ordering::Release);
+        self.actor.stopping();
+        self.actor.destroy();
+    }
+}
+
+#[cfg(all(not(feature = "mailbox"), feature = "async"))]
+impl<A: Actor> ActorHandle<A> {
+    /// # [`ActorHandle::send_later`]
+    /// Schedules `message` to be delivered to this actor once, after `delay`. Dropping (or
+    /// cancelling) the returned [`TaskHandle`] stops the delivery; it is also skipped if the
+    /// actor was killed before the delay elapses.
+    ///
+    /// This checks the handle's own `alive` flag rather than re-validating the generational key
+    /// used to look the actor up, since the scheduled task owns this specific cloned handle and
+    /// so can never end up aliasing a different actor that later reuses the slot.
+    pub fn send_later<M: Message>(&self, message: M, delay: Duration) -> TaskHandle
+    where A: Handler<M> {
+        let handle = self.clone();
+        TaskHandle::new(tokio::spawn(async move {
+            tokio::time::sleep(delay).await;
+            if handle.alive.load(Ordering::Acquire) {
+                let _ = handle.send(message).await;
+            }
+        }))
+    }
+
+    /// # [`ActorHandle::send_interval`]
+    /// Repeatedly constructs a message with `make_message` and delivers it to this actor every
+    /// `period`, until the returned [`TaskHandle`] is dropped or cancelled, or the actor is
+    /// killed.
+    ///
+    /// Like [`send_later`](Self::send_later), this checks the handle's own `alive` flag rather
+    /// than re-validating the generational key, for the same reason: the recurring task owns
+    /// this specific cloned handle, so it can never alias a different actor spawned into a
+    /// reused slot.
+    pub fn send_interval<M, F>(&self, mut make_message: F, period: Duration) -> TaskHandle
+    where
+        A: Handler<M>,
+        M: Message,
+        F: FnMut() -> M + Send + 'static,
+    {
+        let handle = self.clone();
+        TaskHandle::new(tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(period);
+            ticker.tick().await; // the first tick fires immediately; skip it
+
+            loop {
+                ticker.tick().await;
+
+                if !handle.alive.load(Ordering::Acquire) {
+                    return;
+                }
+
+                let _ = handle.send(make_message()).await;
+            }
+        }))
     }
 }
 
+#[cfg(not(feature = "mailbox"))]
 impl<M: Message, A: Actor + Handler<M>> MessageSender<M> for ActorHandle<A> {
     /// Send a message to the actor and wait for a response
     #[cfg(feature = "async")]
     async fn send(&self, message: M) -> M::Result {
-        self.0.handle_message(message).await
+        self.actor.handle_message(message).await
     }
 
     /// Send a message to the actor and wait for a response
     #[cfg(not(feature = "async"))]
     fn send(&self, message: M) -> M::Result {
-        self.0.handle_message(message)
+        self.actor.handle_message(message)
     }
 }
 
+#[cfg(not(feature = "mailbox"))]
 impl<A: Actor> ActorRef for ActorHandle<A> {
     #[inline(always)]
     fn as_any(&self) -> &dyn Any {
@@ -106,15 +237,172 @@ impl<A: Actor> ActorRef for ActorHandle<A> {
 
     #[cfg(not(feature = "async"))]
     fn kill(&self) {
-        self.0.destroy();
+        self.alive.store(false, Ordering::Release);
+        self.actor.stopping();
+        self.actor.destroy();
+    }
+
+    #[cfg(feature = "async")]
+    fn kill(&self) -> Pin<Box<dyn Future<Output = ()> + Send + '_>> {
+        Box::pin(async move {
+            self.alive.store(false, Ordering::Release);
+            self.actor.stopping().await;
+            self.actor.destroy().await;
+        })
+    }
+}
+
+// `A` may not impl Clone, but Arc does.
+#[cfg(not(feature = "mailbox"))]
+impl<A: Actor> Clone for ActorHandle<A> {
+    #[inline(always)]
+    fn clone(&self) -> Self {
+        Self {
+            actor: self.actor.clone(),
+            alive: self.alive.clone(),
+        }
+    }
+}
+
+/// # [`ActorHandle`] (mailbox mode)
+/// Provides functions to send messages to a given actor through its bounded mailbox instead of
+/// calling its handler directly on the caller's task. See [`crate::mailbox`] for the dispatch
+/// details.
+#[cfg(feature = "mailbox")]
+pub struct ActorHandle<A: Actor> {
+    actor: Arc<A>,
+    mailbox: Arc<crate::mailbox::Mailbox<A>>,
+    alive: Arc<AtomicBool>,
+}
+
+#[cfg(feature = "mailbox")]
+impl<A: Actor> ActorHandle<A> {
+    /// # [`ActorHandle::new`]
+    /// Creates a new actor handle, spawning the task that owns the actor and drains its mailbox.
+    pub(crate) fn new(actor: A) -> Self {
+        let actor = Arc::new(actor);
+        let mailbox = Arc::new(crate::mailbox::Mailbox::spawn(actor.clone()));
+        Self { actor, mailbox, alive: Arc::new(AtomicBool::new(true)) }
+    }
+
+    /// # [`ActorHandle::send_later`]
+    /// Schedules `message` to be delivered to this actor once, after `delay`. Dropping (or
+    /// cancelling) the returned [`TaskHandle`] stops the delivery; it is also skipped if the
+    /// actor was killed before the delay elapses.
+    ///
+    /// This checks the handle's own `alive` flag rather than re-validating the generational key
+    /// used to look the actor up, since the scheduled task owns this specific cloned handle and
+    /// so can never end up aliasing a different actor that later reuses the slot.
+    pub fn send_later<M: Message>(&self, message: M, delay: Duration) -> TaskHandle
+    where A: Handler<M> {
+        let handle = self.clone();
+        TaskHandle::new(tokio::spawn(async move {
+            tokio::time::sleep(delay).await;
+            if handle.alive.load(Ordering::Acquire) {
+                let _ = handle.send(message).await;
+            }
+        }))
+    }
+
+    /// # [`ActorHandle::send_interval`]
+    /// Repeatedly constructs a message with `make_message` and delivers it to this actor every
+    /// `period`, until the returned [`TaskHandle`] is dropped or cancelled, or the actor is
+    /// killed.
+    ///
+    /// Like [`send_later`](Self::send_later), this checks the handle's own `alive` flag rather
+    /// than re-validating the generational key, for the same reason: the recurring task owns
+    /// this specific cloned handle, so it can never alias a different actor spawned into a
+    /// reused slot.
+    pub fn send_interval<M, F>(&self, mut make_message: F, period: Duration) -> TaskHandle
+    where
+        A: Handler<M>,
+        M: Message,
+        F: FnMut() -> M + Send + 'static,
+    {
+        let handle = self.clone();
+        TaskHandle::new(tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(period);
+            ticker.tick().await; // the first tick fires immediately; skip it
+
+            loop {
+                ticker.tick().await;
+
+                if !handle.alive.load(Ordering::Acquire) {
+                    return;
+                }
+
+                let _ = handle.send(make_message()).await;
+            }
+        }))
+    }
+
+    /// # [`ActorHandle::send`]
+    /// Send a message to the actor and wait for a response, parking the caller if its mailbox is
+    /// currently full.
+    pub async fn send<M: Message>(&self, message: M) -> M::Result
+    where A: Handler<M> {
+        self.mailbox.send(message).await
+    }
+
+    /// # [`ActorHandle::try_send`]
+    /// Send a message to the actor, failing with [`crate::mailbox::Full`] immediately instead of
+    /// waiting if its mailbox is currently at capacity. On success, await the returned
+    /// [`crate::mailbox::Reply`] for the response.
+    pub fn try_send<M: Message>(&self, message: M) -> Result<crate::mailbox::Reply<M::Result>, crate::mailbox::Full>
+    where A: Handler<M> {
+        self.mailbox.try_send(message)
+    }
+
+    /// # [`ActorHandle::notify`]
+    /// Enqueue a message for the actor without waiting for a reply at all.
+    pub fn notify<M: Message>(&self, message: M)
+    where A: Handler<M> {
+        self.mailbox.notify(message)
+    }
+
+    pub(crate) async fn started(&self) {
+        self.actor.started(self).await;
+    }
+
+    pub async fn kill(&self) {
+        self.alive.store(false, Ordering::Release);
+        self.mailbox.shutdown().await;
+    }
+}
+
+#[cfg(feature = "mailbox")]
+impl<M: Message, A: Actor + Handler<M>> MessageSender<M> for ActorHandle<A> {
+    /// Send a message to the actor and wait for a response
+    async fn send(&self, message: M) -> M::Result {
+        self.mailbox.send(message).await
+    }
+}
+
+#[cfg(feature = "mailbox")]
+impl<A: Actor> ActorRef for ActorHandle<A> {
+    #[inline(always)]
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn kill(&self) -> Pin<Box<dyn Future<Output = ()> + Send + '_>> {
+        Box::pin(async move {
+            self.alive.store(false, Ordering::Release);
+            self.mailbox.shutdown().await;
+        })
     }
 }
 
 // `A` may not impl Clone, but Arc does.
+#[cfg(feature = "mailbox")]
 impl<A: Actor> Clone for ActorHandle<A> {
     #[inline(always)]
     fn clone(&self) -> Self {
-        Self(self.0.clone())
+        Self {
+            actor: self.actor.clone(),
+            mailbox: self.mailbox.clone(),
+            alive: self.alive.clone(),
+        }
     }
 }
 
@@ -125,4 +413,11 @@ pub(crate) trait ActorRef: Send + Sync + 'static {
 
     #[cfg(not(feature = "async"))]
     fn kill(&self);
+
+    /// Object-safe stand-in for the inherent `async fn kill`, needed anywhere a batch of actors
+    /// of possibly different concrete types has to be killed without knowing each one's type,
+    /// such as [`Slacktor::shutdown`](crate::Slacktor::shutdown) and
+    /// [`Slacktor::kill_many`](crate::Slacktor::kill_many).
+    #[cfg(feature = "async")]
+    fn kill(&self) -> Pin<Box<dyn Future<Output = ()> + Send + '_>>;
 }
\ No newline at end of file
@@ -0,0 +1,611 @@
+//! An opt-in, lock-free sharded actor registry.
+//!
+//! [`Slacktor`](crate::Slacktor) requires `&mut self` to spawn or kill actors, which makes it a
+//! single-writer structure: every caller funnels through one serialized point of access.
+//! [`ConcurrentSlacktor`] instead partitions storage into a fixed number of shards chosen at
+//! construction, so many threads can spawn, look up and kill actors through a shared
+//! `&ConcurrentSlacktor` at once. Each thread is assigned a "home" shard the first time it
+//! touches a given registry, and mostly only ever contends with other threads sharing that
+//! shard.
+//!
+//! Each shard owns a growable, append-only list of fixed-size pages of slots. A slot packs a
+//! generation counter and a free-list "next" index into a single atomic word: inserting pops a
+//! slot off the shard's free list (or grows the shard if it's empty) and stamps the slot's
+//! current generation into the returned [`ConcurrentKey`]; removing CASes the generation forward
+//! (immediately invalidating the key) and then links the slot back onto a free list. Because
+//! only the shard that owns a slot may push it onto its *local* free list, killing an actor from
+//! a thread whose home shard differs from the key's shard instead pushes onto a small lock-free
+//! remote-free stack, which the owning shard drains the next time it inserts.
+//!
+//! This preserves the same generational safety [`Slacktor`](crate::Slacktor) gives you (a stale
+//! key can never alias a different actor spawned into the reused slot), but reads and writes
+//! never block behind a global lock.
+
+use core::cell::UnsafeCell;
+use core::ptr;
+use core::sync::atomic::{AtomicPtr, AtomicU64, AtomicUsize, Ordering};
+
+use alloc::boxed::Box;
+use alloc::sync::Arc;
+use alloc::vec::Vec;
+
+extern crate std;
+use std::thread_local;
+
+use crate::actor::{Actor, ActorHandle, ActorRef};
+
+/// How many slots live on each page of a shard.
+const PAGE_SIZE: usize = 128;
+/// How many pages a shard can grow to hold. Pages are only allocated on demand, so this just
+/// bounds the maximum number of actors (`PAGE_SIZE * MAX_PAGES`) a single shard can hold at once.
+const MAX_PAGES: usize = 1024;
+/// Sentinel meaning "the end of the free list" for both a shard's `free_head` and a slot's
+/// packed "next free" field.
+const NIL: usize = usize::MAX;
+
+/// A key into a [`ConcurrentSlacktor`]. Encodes which shard, page and slot an actor lives in,
+/// plus the generation of that slot, so a key can never alias a different actor that later
+/// reuses the same slot.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ConcurrentKey {
+    shard: u32,
+    page: u32,
+    slot: u32,
+    generation: u32,
+}
+
+/// Packs a slot's generation (high 32 bits) and its free-list "next" index (low 32 bits, or
+/// [`u32::MAX`] for "end of list") into one atomic word so both can be updated with a single CAS.
+fn decode(state: u64) -> (u32, u32) {
+    ((state >> 32) as u32, state as u32)
+}
+
+fn encode(generation: u32, next: u32) -> u64 {
+    ((generation as u64) << 32) | next as u64
+}
+
+fn to_next(idx: usize) -> u32 {
+    if idx == NIL { u32::MAX } else { idx as u32 }
+}
+
+fn from_next(next: u32) -> usize {
+    if next == u32::MAX { NIL } else { next as usize }
+}
+
+fn split(idx: usize) -> (usize, usize) {
+    (idx / PAGE_SIZE, idx % PAGE_SIZE)
+}
+
+fn join(page: usize, slot: usize) -> usize {
+    page * PAGE_SIZE + slot
+}
+
+/// A single slot in a page.
+struct Slot {
+    /// See [`decode`]/[`encode`]. CAS'd to claim a slot from the free list, to bump the
+    /// generation on removal, and to splice a freed slot back onto a free list.
+    state: AtomicU64,
+    /// How many `get` calls are currently reading `value`. `remove` spins until this drops to
+    /// zero after bumping the generation, so a reader that already validated the old generation
+    /// can never observe the slot being cleared out from under it.
+    readers: AtomicUsize,
+    value: UnsafeCell<Option<Arc<dyn ActorRef>>>,
+}
+
+// SAFETY: `value` is only read by `get` while registered in `readers` and after matching the
+// generation, and only written by a thread that holds the slot exclusively (an inserter that just
+// claimed it, or `remove` after bumping the generation and waiting out `readers`). That is the
+// synchronization that makes it sound to share a `Slot` across threads; `Arc<dyn ActorRef>` is
+// itself `Send + Sync`.
+unsafe impl Sync for Slot {}
+
+impl Slot {
+    fn new() -> Self {
+        Self {
+            state: AtomicU64::new(encode(0, u32::MAX)),
+            readers: AtomicUsize::new(0),
+            value: UnsafeCell::new(None),
+        }
+    }
+}
+
+/// A fixed-size page of slots. Pages are boxed so growing a shard's page list never moves an
+/// already-published slot, which would invalidate outstanding references into it.
+struct Page {
+    slots: Vec<Slot>,
+}
+
+impl Page {
+    fn new() -> Self {
+        Self {
+            slots: (0..PAGE_SIZE).map(|_| Slot::new()).collect(),
+        }
+    }
+}
+
+/// A lock-free Treiber stack of flat slot indices, used to hand a freed index back to its owning
+/// shard from a thread whose home shard is different.
+struct RemoteFreeStack {
+    head: AtomicPtr<FreeNode>,
+}
+
+struct FreeNode {
+    value: usize,
+    next: *mut FreeNode,
+}
+
+// SAFETY: `FreeNode`s are only ever reached through the atomic `head` pointer, which hands off
+// exclusive ownership on every push/pop.
+unsafe impl Send for FreeNode {}
+
+impl RemoteFreeStack {
+    fn new() -> Self {
+        Self { head: AtomicPtr::new(ptr::null_mut()) }
+    }
+
+    fn push(&self, value: usize) {
+        let node = Box::into_raw(Box::new(FreeNode { value, next: ptr::null_mut() }));
+        loop {
+            let head = self.head.load(Ordering::Acquire);
+            // SAFETY: we uniquely own `node` until the CAS below publishes it.
+            unsafe { (*node).next = head };
+            if self
+                .head
+                .compare_exchange_weak(head, node, Ordering::AcqRel, Ordering::Acquire)
+                .is_ok()
+            {
+                return;
+            }
+        }
+    }
+
+    /// Takes every index pushed so far, leaving the stack empty. Intended to be drained only by
+    /// the shard that owns it.
+    fn drain(&self) -> RemoteFreeDrain {
+        RemoteFreeDrain { next: self.head.swap(ptr::null_mut(), Ordering::AcqRel) }
+    }
+}
+
+impl Drop for RemoteFreeStack {
+    fn drop(&mut self) {
+        // Reclaim every node still on the stack; nothing else can be touching it while we're
+        // being dropped.
+        for _ in self.drain() {}
+    }
+}
+
+struct RemoteFreeDrain {
+    next: *mut FreeNode,
+}
+
+impl Iterator for RemoteFreeDrain {
+    type Item = usize;
+
+    fn next(&mut self) -> Option<usize> {
+        if self.next.is_null() {
+            return None;
+        }
+
+        // SAFETY: `self.next` was either the stack's swapped-out head, or the `next` pointer of
+        // a node we just took ownership of below; either way nothing else can reach it.
+        let node = unsafe { Box::from_raw(self.next) };
+        self.next = node.next;
+        Some(node.value)
+    }
+}
+
+/// One partition of a [`ConcurrentSlacktor`]'s storage.
+struct Shard {
+    /// Lazily-allocated, append-only pages. A null entry means that page hasn't been needed yet.
+    pages: Box<[AtomicPtr<Page>]>,
+    /// How many slots have ever been carved out of this shard (i.e. the high-water mark used to
+    /// grow the shard when its free list is empty).
+    len: AtomicUsize,
+    /// Head of this shard's local free list, or [`NIL`], packed with a tag (high 32 bits,
+    /// incremented on every push/pop) so a CAS can't be fooled by another thread popping an
+    /// index and pushing it right back before the CAS lands (the classic Treiber-stack ABA
+    /// problem). See [`encode`]/[`decode`].
+    free_head: AtomicU64,
+    /// Slots freed by a thread whose home shard isn't this one. Drained into `free_head` the
+    /// next time this shard inserts.
+    remote_freed: RemoteFreeStack,
+}
+
+impl Shard {
+    fn new() -> Self {
+        Self {
+            pages: (0..MAX_PAGES).map(|_| AtomicPtr::new(ptr::null_mut())).collect(),
+            len: AtomicUsize::new(0),
+            free_head: AtomicU64::new(encode(0, to_next(NIL))),
+            remote_freed: RemoteFreeStack::new(),
+        }
+    }
+
+    fn page(&self, page: usize) -> Option<&Page> {
+        let ptr = self.pages.get(page)?.load(Ordering::Acquire);
+        if ptr.is_null() {
+            None
+        } else {
+            // SAFETY: once published, a page is never moved or freed for the shard's lifetime.
+            Some(unsafe { &*ptr })
+        }
+    }
+
+    /// Returns the page at `page`, allocating it on first use. `None` if `page` is past
+    /// `MAX_PAGES`, i.e. the shard has hit its `PAGE_SIZE * MAX_PAGES` capacity.
+    fn ensure_page(&self, page: usize) -> Option<&Page> {
+        if let Some(existing) = self.page(page) {
+            return Some(existing);
+        }
+
+        if page >= self.pages.len() {
+            return None;
+        }
+
+        let new_page = Box::into_raw(Box::new(Page::new()));
+        match self.pages[page].compare_exchange(
+            ptr::null_mut(),
+            new_page,
+            Ordering::AcqRel,
+            Ordering::Acquire,
+        ) {
+            // SAFETY: we just published `new_page`.
+            Ok(_) => Some(unsafe { &*new_page }),
+            Err(existing) => {
+                // Another thread beat us to it; drop our redundant allocation.
+                // SAFETY: we still uniquely own `new_page`, nobody else observed it.
+                unsafe { drop(Box::from_raw(new_page)) };
+                // SAFETY: `existing` is the page the winning thread published.
+                Some(unsafe { &*existing })
+            }
+        }
+    }
+
+    fn push_free(&self, idx: usize) {
+        let (page, slot) = split(idx);
+        let slot = &self.page(page).expect("freed slot's page must already exist").slots[slot];
+
+        loop {
+            let head_state = self.free_head.load(Ordering::Acquire);
+            let (tag, next) = decode(head_state);
+            let head = from_next(next);
+
+            // Publish the slot's link to the rest of the free list before splicing it in.
+            let state = slot.state.load(Ordering::Acquire);
+            let (generation, _) = decode(state);
+            slot.state.store(encode(generation, to_next(head)), Ordering::Release);
+
+            // Bump the tag on every attempt (successful or not) so a thread that popped `idx`
+            // and is about to push it back can never produce a state this CAS would mistake
+            // for the one it last observed.
+            let new_head_state = encode(tag.wrapping_add(1), to_next(idx));
+            if self
+                .free_head
+                .compare_exchange_weak(head_state, new_head_state, Ordering::AcqRel, Ordering::Acquire)
+                .is_ok()
+            {
+                return;
+            }
+        }
+    }
+
+    /// Inserts `value` into a free slot, growing the shard if needed. Returns `None` if the
+    /// shard is already at its `PAGE_SIZE * MAX_PAGES` capacity and has no free slot to reuse;
+    /// the index claimed by `len.fetch_add` in that case is simply never allocated a page and
+    /// stays permanently unused, since nothing else can be holding a key into it.
+    fn insert(&self, value: Arc<dyn ActorRef>) -> Option<(usize, usize, u32)> {
+        // Reclaim anything freed by other shards' home threads before falling back to growth.
+        for idx in self.remote_freed.drain() {
+            self.push_free(idx);
+        }
+
+        loop {
+            let head_state = self.free_head.load(Ordering::Acquire);
+            let (tag, next) = decode(head_state);
+            let head = from_next(next);
+
+            if head == NIL {
+                let idx = self.len.fetch_add(1, Ordering::Relaxed);
+                let (page, slot_idx) = split(idx);
+                let slot = &self.ensure_page(page)?.slots[slot_idx];
+                // SAFETY: this slot was never reachable from any free list, so we're the only
+                // ones who will ever touch `value` until the first `remove` call on it.
+                unsafe { *slot.value.get() = Some(value) };
+                return Some((page, slot_idx, 0));
+            }
+
+            let (page, slot_idx) = split(head);
+            let slot = &self.page(page).expect("free list points at an unallocated page").slots[slot_idx];
+            let state = slot.state.load(Ordering::Acquire);
+            let (generation, next) = decode(state);
+
+            // Bump the tag on every attempt so a concurrent pop-then-push of the same `head`
+            // index can't produce a state this CAS mistakes for the one we just read.
+            let new_head_state = encode(tag.wrapping_add(1), next);
+            if self
+                .free_head
+                .compare_exchange_weak(head_state, new_head_state, Ordering::AcqRel, Ordering::Acquire)
+                .is_ok()
+            {
+                // SAFETY: we just won the CAS that unlinked this slot from the free list, so no
+                // other inserter can reach it, and `remove` doesn't touch `value` until it has
+                // observed this slot is occupied again (a fresh, higher generation).
+                unsafe { *slot.value.get() = Some(value) };
+                return Some((page, slot_idx, generation));
+            }
+        }
+    }
+
+    fn remove(&self, page: usize, slot_idx: usize, generation: u32, home: bool) -> Option<Arc<dyn ActorRef>> {
+        let slot = &self.page(page)?.slots[slot_idx];
+
+        loop {
+            let state = slot.state.load(Ordering::Acquire);
+            let (current_generation, next) = decode(state);
+
+            if current_generation != generation {
+                return None;
+            }
+
+            let bumped = encode(current_generation.wrapping_add(1), next);
+            // `SeqCst`, not `AcqRel`: this bump and `get`'s `readers` increment are a Dekker-style
+            // mutual-exclusion handshake between two *independent* atomics (this one and
+            // `readers`), and nothing short of a total store order rules out the StoreLoad
+            // reorder where `get` observes the pre-bump generation while this call's spin below
+            // observes `readers == 0` — the one reordering x86-TSO's store buffering (and
+            // ARM/POWER outright) still permit for a pair of plain acquire/release atomics.
+            if slot
+                .state
+                .compare_exchange_weak(state, bumped, Ordering::SeqCst, Ordering::SeqCst)
+                .is_ok()
+            {
+                break;
+            }
+        }
+
+        // Wait out any reader that validated the old generation before we bumped it.
+        while slot.readers.load(Ordering::SeqCst) != 0 {
+            core::hint::spin_loop();
+        }
+
+        // SAFETY: the generation bump above means no reader that starts from here on will match
+        // `generation`, and we just waited out every reader that started before it.
+        let value = unsafe { &mut *slot.value.get() }.take();
+
+        let idx = join(page, slot_idx);
+        if home {
+            self.push_free(idx);
+        } else {
+            self.remote_freed.push(idx);
+        }
+
+        value
+    }
+
+    fn get<A: Actor>(&self, page: usize, slot_idx: usize, generation: u32) -> Option<ActorHandle<A>> {
+        let slot = &self.page(page)?.slots[slot_idx];
+
+        // `SeqCst`, not `AcqRel`: this increment and `remove`'s generation-bump CAS are a
+        // Dekker-style handshake across two independent atomics (`readers` and `state`), which
+        // plain acquire/release can't make safe on its own — see the matching comment in
+        // `remove`. The `state` load just below must be `SeqCst` too, for the same reason.
+        slot.readers.fetch_add(1, Ordering::SeqCst);
+
+        let state = slot.state.load(Ordering::SeqCst);
+        let (current_generation, _) = decode(state);
+
+        let result = if current_generation == generation {
+            // SAFETY: we're registered as a reader, and `remove` won't touch `value` until every
+            // reader registered before its generation bump (which this generation check rules
+            // out having missed) has released.
+            unsafe { &*slot.value.get() }
+                .as_ref()
+                .and_then(|actor| actor.as_any().downcast_ref::<ActorHandle<A>>())
+                .cloned()
+        } else {
+            None
+        };
+
+        slot.readers.fetch_sub(1, Ordering::Release);
+
+        result
+    }
+}
+
+impl Drop for Shard {
+    fn drop(&mut self) {
+        // Reclaim every page this shard ever allocated. Dropping each `Page` in turn drops its
+        // `Slot`s, which drops whatever `Arc<dyn ActorRef>` is still parked in an occupied one,
+        // running that actor's destructor.
+        for page in self.pages.iter() {
+            let ptr = page.load(Ordering::Relaxed);
+            if !ptr.is_null() {
+                // SAFETY: we have exclusive access to the shard (and so every page it published)
+                // while being dropped, and no page pointer is ever published more than once.
+                unsafe { drop(Box::from_raw(ptr)) };
+            }
+        }
+    }
+}
+
+thread_local! {
+    /// The affinity id this thread was first assigned by any [`ConcurrentSlacktor`]. Stable for
+    /// the lifetime of the thread, and reduced modulo a registry's shard count to pick its home.
+    static AFFINITY: usize = NEXT_AFFINITY.fetch_add(1, Ordering::Relaxed);
+}
+
+static NEXT_AFFINITY: AtomicUsize = AtomicUsize::new(0);
+
+fn home_shard(shards: usize) -> usize {
+    AFFINITY.with(|id| id % shards)
+}
+
+/// A lock-free, sharded [`Slacktor`](crate::Slacktor)-alike that allows spawning, looking up and
+/// killing actors through a shared reference from many threads at once.
+pub struct ConcurrentSlacktor {
+    shards: Box<[Shard]>,
+}
+
+impl ConcurrentSlacktor {
+    /// Creates a new [`ConcurrentSlacktor`] partitioned into `shards` shards. A good default is
+    /// the number of cores expected to touch the registry concurrently.
+    pub fn new(shards: usize) -> Self {
+        assert!(shards > 0, "a ConcurrentSlacktor needs at least one shard");
+
+        Self {
+            shards: (0..shards).map(|_| Shard::new()).collect(),
+        }
+    }
+
+    /// Create a new actor and return a [`ConcurrentKey`] that can be used to look it up or kill
+    /// it from any thread. Once the actor is installed and its key is known, its
+    /// [`Actor::started`] hook is called.
+    ///
+    /// Returns `None` if the actor's home shard is already at capacity (`PAGE_SIZE * MAX_PAGES`,
+    /// currently 128 * 1024 = 131072 live actors on that one shard) and has no freed slot to
+    /// reuse. Unlike [`Slacktor`](crate::Slacktor), a shard never grows past this; pick a shard
+    /// count that spreads your workload's peak concurrency widely enough to stay well under it.
+    #[cfg(feature = "async")]
+    pub async fn spawn<A: Actor>(&self, actor: A) -> Option<ConcurrentKey> {
+        let key = self.insert(actor)?;
+
+        self.get::<A>(key).expect("actor was just inserted").started().await;
+
+        Some(key)
+    }
+
+    /// Create a new actor and return a [`ConcurrentKey`] that can be used to look it up or kill
+    /// it from any thread. Once the actor is installed and its key is known, its
+    /// [`Actor::started`] hook is called.
+    ///
+    /// Returns `None` if the actor's home shard is already at capacity (`PAGE_SIZE * MAX_PAGES`,
+    /// currently 128 * 1024 = 131072 live actors on that one shard) and has no freed slot to
+    /// reuse. Unlike [`Slacktor`](crate::Slacktor), a shard never grows past this; pick a shard
+    /// count that spreads your workload's peak concurrency widely enough to stay well under it.
+    #[cfg(not(feature = "async"))]
+    pub fn spawn<A: Actor>(&self, actor: A) -> Option<ConcurrentKey> {
+        let key = self.insert(actor)?;
+
+        self.get::<A>(key).expect("actor was just inserted").started();
+
+        Some(key)
+    }
+
+    /// Inserts a newly-created actor's handle into its home shard and returns its key. Shared by
+    /// every `spawn` variant. `None` if that shard is already at capacity; see [`Self::spawn`].
+    fn insert<A: Actor>(&self, actor: A) -> Option<ConcurrentKey> {
+        let shard_id = home_shard(self.shards.len());
+        let value: Arc<dyn ActorRef> = Arc::new(ActorHandle::new(actor));
+        let (page, slot, generation) = self.shards[shard_id].insert(value)?;
+
+        Some(ConcurrentKey {
+            shard: shard_id as u32,
+            page: page as u32,
+            slot: slot as u32,
+            generation,
+        })
+    }
+
+    /// Get an actor handle given its [`ConcurrentKey`].
+    /// Returns [`None`] if the given actor does not exist, its generation is stale, or it is not
+    /// an actor of type `A`.
+    pub fn get<A: Actor>(&self, key: ConcurrentKey) -> Option<ActorHandle<A>> {
+        self.shards
+            .get(key.shard as usize)?
+            .get::<A>(key.page as usize, key.slot as usize, key.generation)
+    }
+
+    /// Remove's the registry's reference to a given actor and calls the actor's `kill` function.
+    /// Does nothing if `key` does not point at a live actor.
+    #[cfg(not(feature = "async"))]
+    pub fn kill(&self, key: ConcurrentKey) {
+        let Some(shard) = self.shards.get(key.shard as usize) else {
+            return;
+        };
+
+        let home = home_shard(self.shards.len()) == key.shard as usize;
+        if let Some(actor) = shard.remove(key.page as usize, key.slot as usize, key.generation, home) {
+            actor.kill();
+        }
+    }
+
+    /// Remove's the registry's reference to a given actor and calls the actor's `kill` function.
+    /// Returns [`None`] if `key` does not point at a live actor of type `A`.
+    #[cfg(feature = "async")]
+    pub async fn kill<A: Actor>(&self, key: ConcurrentKey) -> Option<()> {
+        let shard = self.shards.get(key.shard as usize)?;
+        let home = home_shard(self.shards.len()) == key.shard as usize;
+        let actor = shard.remove(key.page as usize, key.slot as usize, key.generation, home)?;
+        let actor = actor.as_any().downcast_ref::<ActorHandle<A>>()?;
+
+        actor.kill().await;
+
+        Some(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::thread;
+
+    struct NoOp;
+    impl Actor for NoOp {}
+
+    const THREADS: usize = 8;
+    const ITERATIONS: usize = 2000;
+
+    /// Hammers a handful of shards with more threads than shards, so every shard sees concurrent
+    /// `spawn`/`get`/`kill` from the threads that share it as their home shard. Each thread only
+    /// kills keys it spawned itself, so kills go through the local free list, not the remote-free
+    /// path. Run under `--release` for enough iterations to make a reader/generation race (the
+    /// one this module's `SeqCst` handshake guards against) or a free-list double-alloc show up.
+    #[cfg(not(feature = "async"))]
+    #[test]
+    fn concurrent_spawn_get_kill_stress() {
+        let system = Arc::new(ConcurrentSlacktor::new(THREADS / 2));
+
+        let handles: Vec<_> = (0..THREADS)
+            .map(|_| {
+                let system = system.clone();
+                thread::spawn(move || {
+                    for _ in 0..ITERATIONS {
+                        let key = system.spawn(NoOp).expect("shard has room for this stress test");
+                        assert!(system.get::<NoOp>(key).is_some(), "freshly spawned actor must be reachable");
+                        system.kill(key);
+                        assert!(system.get::<NoOp>(key).is_none(), "killed key must not alias the next occupant");
+                    }
+                })
+            })
+            .collect();
+
+        for handle in handles {
+            handle.join().expect("stress thread panicked");
+        }
+    }
+
+    /// Async-feature counterpart of [`concurrent_spawn_get_kill_stress`]; see its doc comment.
+    #[cfg(feature = "async")]
+    #[tokio::test(flavor = "multi_thread", worker_threads = 8)]
+    async fn concurrent_spawn_get_kill_stress() {
+        let system = Arc::new(ConcurrentSlacktor::new(THREADS / 2));
+
+        let tasks: Vec<_> = (0..THREADS)
+            .map(|_| {
+                let system = system.clone();
+                tokio::spawn(async move {
+                    for _ in 0..ITERATIONS {
+                        let key = system.spawn(NoOp).await.expect("shard has room for this stress test");
+                        assert!(system.get::<NoOp>(key).is_some(), "freshly spawned actor must be reachable");
+                        system.kill::<NoOp>(key).await;
+                        assert!(system.get::<NoOp>(key).is_none(), "killed key must not alias the next occupant");
+                    }
+                })
+            })
+            .collect();
+
+        for task in tasks {
+            task.await.expect("stress task panicked");
+        }
+    }
+}
@@ -3,13 +3,35 @@
 extern crate alloc;
 
 use alloc::sync::Arc;
+use alloc::vec::Vec;
+
+#[cfg(feature = "async")]
+use alloc::boxed::Box;
+#[cfg(feature = "async")]
+use core::future::Future;
+#[cfg(feature = "async")]
+use core::pin::Pin;
+#[cfg(feature = "async")]
+use core::task::Poll;
 
 
 pub mod actor;
+pub mod slab;
+#[cfg(feature = "concurrent")]
+pub mod concurrent;
+#[cfg(feature = "mailbox")]
+pub mod mailbox;
+#[cfg(any(feature = "mailbox", feature = "async"))]
+pub mod schedule;
 
 pub use actor::{
     Actor, ActorHandle, Message, MessageSender
 };
+pub use slab::SlabKey;
+#[cfg(feature = "concurrent")]
+pub use concurrent::{ConcurrentKey, ConcurrentSlacktor};
+#[cfg(any(feature = "mailbox", feature = "async"))]
+pub use schedule::TaskHandle;
 
 use actor::ActorRef;
 
@@ -23,38 +45,104 @@ pub struct Slacktor {
 impl Slacktor {
     /// # [`Slacktor::new`]
     /// Creates a new [`Slacktor`] instance
-    pub const fn new() -> Self {
+    pub fn new() -> Self {
         Self {
-            slab: slab::Slab::new(),
+            slab: slab::Slab::new(0),
         }
     }
 
-    /// # [`Slacktor::next_id`]
-    /// Returns what the id of the next actor will be
-    pub fn next_id(&self) -> usize {
-        self.slab.vacant_key() as usize
+    /// # [`Slacktor::spawn`]
+    /// Create a new actor and return a [`SlabKey`] that can be used to look it up or kill it.
+    /// Because the key carries the slot's generation, a key from a since-killed actor will never
+    /// alias a different actor that gets spawned into the same slot. Keys issued before a
+    /// [`Slacktor::shutdown`] are the exception: shutdown clears every slot together with its
+    /// generation, so such a key can address an actor spawned afterwards. Once the actor is
+    /// installed and its key is known, its [`Actor::started`] hook is called.
+    #[cfg(feature = "async")]
+    pub async fn spawn<A: Actor>(&mut self, actor: A) -> SlabKey {
+        let key = self.insert(actor);
+
+        self.get::<A>(key).expect("actor was just inserted").started().await;
+
+        key
     }
 
     /// # [`Slacktor::spawn`]
-    /// Create a new actor and return it's id.
-    pub fn spawn<A: Actor>(&mut self, actor: A) -> usize {
-        self.slab.insert(Arc::new(ActorHandle::new(actor)))
+    /// Create a new actor and return a [`SlabKey`] that can be used to look it up or kill it.
+    /// Because the key carries the slot's generation, a key from a since-killed actor will never
+    /// alias a different actor that gets spawned into the same slot. Keys issued before a
+    /// [`Slacktor::shutdown`] are the exception: shutdown clears every slot together with its
+    /// generation, so such a key can address an actor spawned afterwards. Once the actor is
+    /// installed and its key is known, its [`Actor::started`] hook is called.
+    #[cfg(not(feature = "async"))]
+    pub fn spawn<A: Actor>(&mut self, actor: A) -> SlabKey {
+        let key = self.insert(actor);
+
+        self.get::<A>(key).expect("actor was just inserted").started();
+
+        key
+    }
+
+    /// # [`Slacktor::spawn_many`]
+    /// Spawns every actor yielded by `actors` in one pass, pre-reserving slab capacity from the
+    /// iterator's lower size-hint bound so the slab doesn't have to grow repeatedly mid-batch, and
+    /// returns their keys in the same order the actors were yielded.
+    #[cfg(feature = "async")]
+    pub async fn spawn_many<A: Actor>(&mut self, actors: impl IntoIterator<Item = A>) -> Vec<SlabKey> {
+        let actors = actors.into_iter();
+        let (lower, _) = actors.size_hint();
+        self.slab.increase_capacity(Some(lower));
+
+        let mut keys = Vec::with_capacity(lower);
+        for actor in actors {
+            keys.push(self.spawn(actor).await);
+        }
+        keys
+    }
+
+    /// # [`Slacktor::spawn_many`]
+    /// Spawns every actor yielded by `actors` in one pass, pre-reserving slab capacity from the
+    /// iterator's lower size-hint bound so the slab doesn't have to grow repeatedly mid-batch, and
+    /// returns their keys in the same order the actors were yielded.
+    #[cfg(not(feature = "async"))]
+    pub fn spawn_many<A: Actor>(&mut self, actors: impl IntoIterator<Item = A>) -> Vec<SlabKey> {
+        let actors = actors.into_iter();
+        let (lower, _) = actors.size_hint();
+        self.slab.increase_capacity(Some(lower));
+
+        let mut keys = Vec::with_capacity(lower);
+        for actor in actors {
+            keys.push(self.spawn(actor));
+        }
+        keys
+    }
+
+    /// Inserts a newly-created actor's handle into the slab, growing it if necessary, and
+    /// returns its key. Shared by every `spawn` variant.
+    fn insert<A: Actor>(&mut self, actor: A) -> SlabKey {
+        let handle: Arc<dyn ActorRef> = Arc::new(ActorHandle::new(actor));
+
+        // Try to insert without growing first, since that's the common case.
+        if let Some(key) = self.slab.insert(handle.clone()) {
+            return key;
+        }
+
+        // The slab is full; grow it and retry. This cannot fail.
+        self.slab.increase_capacity(None);
+        self.slab.insert(handle).expect("slab should have spare capacity after growing")
     }
 
     /// # [`Slacktor::kill`]
     /// Remove's the Slacktor instance's reference to a given actor and calls the actor's `kill` function.
     /// This will cause the actor to be destroyed after every existing handle is dropped,
     /// which may or may not happen. Generally an actor will deinitialize itself, and then respond with an error
-    /// to every additional message.
+    /// to every additional message. Does nothing if `key` does not point at a live actor (for example,
+    /// because it was already killed, or because its generation is stale).
     #[cfg(not(feature = "async"))]
-    pub fn kill(&mut self, id: usize) {
-        // If the actor does not exist, exit early
-        if !self.slab.contains(id) {
+    pub fn kill(&mut self, key: SlabKey) {
+        let Some(a) = self.slab.remove(&key) else {
             return;
-        }
-
-        // Remove the actor from the slab
-        let a = self.slab.remove(id);
+        };
 
         // Kill it
         a.kill();
@@ -64,16 +152,11 @@ impl Slacktor {
     /// Remove's the Slacktor instance's reference to a given actor and calls the actor's `kill` function.
     /// This will cause the actor to be destroyed after every existing handle is dropped,
     /// which may or may not happen. Generally an actor will deinitialize itself, and then respond with an error
-    /// to every additional message. Returns [`None`] if the actor did not exist
+    /// to every additional message. Returns [`None`] if `key` does not point at a live actor (for example,
+    /// because it was already killed, its generation is stale, or it is not an actor of type `A`).
     #[cfg(feature = "async")]
-    pub async fn kill<A: Actor>(&mut self, id: usize) -> Option<()> {
-        // If the actor does not exist, exit early
-        if !self.slab.contains(id) {
-            return None;
-        }
-
-        // Remove the actor from the slab
-        let a = self.slab.remove(id);
+    pub async fn kill<A: Actor>(&mut self, key: SlabKey) -> Option<()> {
+        let a = self.slab.remove(&key)?;
         let a = a.as_any().downcast_ref::<ActorHandle<A>>()?;
 
         // Kill it
@@ -82,11 +165,48 @@ impl Slacktor {
         Some(())
     }
 
+    /// # [`Slacktor::kill_many`]
+    /// Kills every actor named by `keys` in one pass: the matching handles are all removed from
+    /// the slab first, and only then are their `destroy` futures run, concurrently rather than
+    /// one after another like [`Slacktor::shutdown`] does. Actors may be of different concrete
+    /// types, same as [`Slacktor::shutdown`]. Keys that don't point at a live actor (already
+    /// killed, or stale generation) are silently skipped.
+    #[cfg(feature = "async")]
+    pub async fn kill_many(&mut self, keys: impl IntoIterator<Item = SlabKey>) {
+        let removed: Vec<Arc<dyn ActorRef>> = keys
+            .into_iter()
+            .filter_map(|key| self.slab.remove(&key))
+            .collect();
+
+        let futures = removed
+            .iter()
+            .map(|a| -> Pin<Box<dyn Future<Output = ()> + Send + '_>> { a.kill() })
+            .collect();
+
+        join_all(futures).await;
+    }
+
+    /// # [`Slacktor::kill_many`]
+    /// Kills every actor named by `keys` in one pass: the matching handles are all removed from
+    /// the slab first, and only then is each one's `kill` called. Keys that don't point at a live
+    /// actor are silently skipped.
+    #[cfg(not(feature = "async"))]
+    pub fn kill_many(&mut self, keys: impl IntoIterator<Item = SlabKey>) {
+        let removed: Vec<Arc<dyn ActorRef>> = keys
+            .into_iter()
+            .filter_map(|key| self.slab.remove(&key))
+            .collect();
+
+        for a in removed {
+            a.kill();
+        }
+    }
+
     /// # [`Slacktor::get`]
-    /// Get an actor handle given its id.
-    /// Return's [`None`] if the given actor does not exist.
-    pub fn get<A: Actor>(&self, id: usize) -> Option<&ActorHandle<A>> {
-        self.slab.get(id)
+    /// Get an actor handle given its [`SlabKey`].
+    /// Return's [`None`] if the given actor does not exist, or its generation is stale.
+    pub fn get<A: Actor>(&self, key: SlabKey) -> Option<&ActorHandle<A>> {
+        self.slab.get(&key)
             .and_then(|actor| actor.as_any().downcast_ref())
     }
 
@@ -117,4 +237,24 @@ impl Slacktor {
     pub fn shrink(&mut self) {
         self.slab.shrink_to_fit();
     }
+}
+
+/// Runs a batch of boxed futures concurrently on the calling task, completing once every one of
+/// them has. Used by [`Slacktor::kill_many`] to run a batch of `destroy` futures side by side
+/// instead of awaiting them one at a time.
+#[cfg(feature = "async")]
+async fn join_all(mut futures: Vec<Pin<Box<dyn Future<Output = ()> + Send + '_>>>) {
+    core::future::poll_fn(move |cx| {
+        let mut i = 0;
+        while i < futures.len() {
+            if futures[i].as_mut().poll(cx).is_ready() {
+                let _ = futures.swap_remove(i);
+            } else {
+                i += 1;
+            }
+        }
+
+        if futures.is_empty() { Poll::Ready(()) } else { Poll::Pending }
+    })
+    .await
 }
\ No newline at end of file
@@ -0,0 +1,137 @@
+//! Bounded-mailbox actor dispatch, gated behind the `mailbox` feature (which implies `async`).
+//!
+//! In the default direct-dispatch model, [`ActorHandle::send`](crate::actor::ActorHandle::send)
+//! runs the handler inline on the caller's own task, so one slow actor stalls every caller
+//! currently waiting on it, with no queuing or fairness between them. With `mailbox` enabled,
+//! [`Slacktor::spawn`](crate::Slacktor::spawn) instead starts a task that owns the actor and
+//! drains a bounded channel of pending messages one at a time, and `ActorHandle` sends messages
+//! through the channel's sending half instead of calling the actor directly. A full mailbox then applies natural
+//! backpressure: [`Mailbox::send`] parks the caller until the actor's task makes room, which is
+//! exactly what the direct-call model can't do.
+
+use alloc::boxed::Box;
+use alloc::sync::Arc;
+use core::future::Future;
+use core::pin::Pin;
+use core::task::{Context, Poll};
+
+use tokio::sync::{mpsc, oneshot};
+
+use crate::actor::{Actor, Handler, Message};
+
+/// Returned by [`Mailbox::try_send`] when the mailbox is at capacity.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Full;
+
+/// The response to a message sent with [`Mailbox::try_send`]. Await it to get `M::Result` once
+/// the actor's task has processed the message.
+pub struct Reply<T>(oneshot::Receiver<T>);
+
+impl<T> Future for Reply<T> {
+    type Output = T;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<T> {
+        Pin::new(&mut self.0)
+            .poll(cx)
+            .map(|result| result.expect("actor task dropped the reply without responding"))
+    }
+}
+
+/// A boxed, type-erased unit of work: given a reference to the actor, run the matching
+/// [`Handler`] and fulfill (or discard) its reply.
+type Job<A> = Box<dyn for<'a> FnOnce(&'a A) -> Pin<Box<dyn Future<Output = ()> + Send + 'a>> + Send>;
+
+fn job_for<A, M>(message: M, reply: oneshot::Sender<M::Result>) -> Job<A>
+where
+    A: Handler<M>,
+    M: Message,
+{
+    Box::new(move |actor: &A| {
+        Box::pin(async move {
+            let result = actor.handle_message(message).await;
+            let _ = reply.send(result);
+        })
+    })
+}
+
+/// The mailbox side of a mailbox-mode [`ActorHandle`](crate::actor::ActorHandle): owns the
+/// sending half of the channel feeding the actor's task.
+pub(crate) struct Mailbox<A: Actor> {
+    sender: mpsc::Sender<Job<A>>,
+}
+
+impl<A: Actor> Mailbox<A> {
+    /// Spawns the task that owns `actor` and drains its mailbox, and returns the sending half.
+    pub(crate) fn spawn(actor: Arc<A>) -> Self {
+        let (sender, mut receiver) = mpsc::channel::<Job<A>>(A::MAILBOX_CAPACITY);
+
+        tokio::spawn(async move {
+            while let Some(job) = receiver.recv().await {
+                job(&actor).await;
+            }
+        });
+
+        Self { sender }
+    }
+
+    /// Send a message to the actor and wait for a response, parking if the mailbox is full.
+    pub(crate) async fn send<M: Message>(&self, message: M) -> M::Result
+    where
+        A: Handler<M>,
+    {
+        let (reply_tx, reply_rx) = oneshot::channel();
+
+        self.sender
+            .send(job_for(message, reply_tx))
+            .await
+            .expect("actor task should outlive its ActorHandle");
+
+        reply_rx.await.expect("actor task dropped the reply without responding")
+    }
+
+    /// Send a message to the actor without waiting for mailbox space, failing immediately if
+    /// it's full.
+    pub(crate) fn try_send<M: Message>(&self, message: M) -> Result<Reply<M::Result>, Full>
+    where
+        A: Handler<M>,
+    {
+        let (reply_tx, reply_rx) = oneshot::channel();
+
+        self.sender
+            .try_send(job_for(message, reply_tx))
+            .map_err(|_| Full)?;
+
+        Ok(Reply(reply_rx))
+    }
+
+    /// Enqueue a message without waiting for a reply at all. Best-effort: if the mailbox is
+    /// full, the message is silently dropped, same as it would be for a dead actor.
+    pub(crate) fn notify<M: Message>(&self, message: M)
+    where
+        A: Handler<M>,
+    {
+        let (reply_tx, _) = oneshot::channel();
+        let _ = self.sender.try_send(job_for(message, reply_tx));
+    }
+
+    /// Runs the actor's `stopping`/`destroy` hooks on the mailbox task, behind every message
+    /// queued ahead of it, instead of calling them directly on the shared actor. Messages and
+    /// teardown are both run one at a time by the actor's own task, so this keeps teardown from
+    /// racing an in-flight `handle_message` call on the same actor.
+    pub(crate) async fn shutdown(&self) {
+        let (done_tx, done_rx) = oneshot::channel();
+        let job: Job<A> = Box::new(move |actor: &A| {
+            Box::pin(async move {
+                actor.stopping().await;
+                actor.destroy().await;
+                let _ = done_tx.send(());
+            })
+        });
+
+        // If the task has already exited (all senders dropped elsewhere), there's nothing left
+        // to shut down.
+        if self.sender.send(job).await.is_ok() {
+            let _ = done_rx.await;
+        }
+    }
+}
@@ -0,0 +1,27 @@
+//! Delayed and recurring message delivery for actor handles
+//! ([`ActorHandle::send_later`](crate::actor::ActorHandle::send_later)/
+//! [`send_interval`](crate::actor::ActorHandle::send_interval)), gated behind the `async`
+//! feature.
+
+/// A handle to a scheduled delivery started by
+/// [`ActorHandle::send_later`](crate::actor::ActorHandle::send_later) or
+/// [`ActorHandle::send_interval`](crate::actor::ActorHandle::send_interval). Dropping it (or
+/// calling [`TaskHandle::cancel`] explicitly) stops the timer immediately.
+pub struct TaskHandle(tokio::task::JoinHandle<()>);
+
+impl TaskHandle {
+    pub(crate) fn new(join: tokio::task::JoinHandle<()>) -> Self {
+        Self(join)
+    }
+
+    /// Stops the timer immediately. Equivalent to dropping the handle.
+    pub fn cancel(self) {
+        self.0.abort();
+    }
+}
+
+impl Drop for TaskHandle {
+    fn drop(&mut self) {
+        self.0.abort();
+    }
+}
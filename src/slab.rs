@@ -1,3 +1,4 @@
+use alloc::vec::Vec;
 
 /// # SlabKey
 /// A generational index used as the key to a [`ActorSlab`], which allows for actor slots to be reused without worrying about accessing an incorrect actor.
@@ -46,6 +47,13 @@ pub struct Slab<T> {
     initial_capacity: usize,
 }
 
+impl<T> Default for Slab<T> {
+    /// Creates an empty [`Slab`] with no initial capacity.
+    fn default() -> Self {
+        Self::new(0)
+    }
+}
+
 impl<T> Slab<T> {
     /// Creates a new [`Slab`] initialized to the given capacity.
     pub fn new(capacity: usize) -> Self {
@@ -122,40 +130,63 @@ impl<T> Slab<T> {
         }
     }
 
-    /// Remove an entry from the slab
-    pub fn remove(&mut self, key: &SlabKey) {
-        // Get the entry. If it doesn't exist, then we can return (because it never existed in the first place).
-        let Some(slab_entry) = self.entries.get_mut(key.index) else {
-            return;
+    /// Removes an entry from the slab and returns it, invalidating `key` (and every other
+    /// [`SlabKey`] pointing at that slot) in the process.
+    /// Returns [`None`] if the slot is already free or `key`'s generation is stale.
+    pub fn remove(&mut self, key: &SlabKey) -> Option<T> {
+        // Get the entry. If it doesn't exist, then we can return None (because it never existed in the first place).
+        let slab_entry = self.entries.get_mut(key.index)?;
+
+        // If the key's generation does not match, then ignore the removal.
+        if slab_entry.generation != key.generation {
+            return None;
+        }
+
+        // Bail out if the slot is already free, leaving it untouched.
+        if let Entry::Free(_) = slab_entry.entry {
+            return None;
+        }
+
+        // Swap in a free entry pointing to next_free, pulling the used value back out.
+        let Entry::Used(value) = core::mem::replace(&mut slab_entry.entry, Entry::Free(self.next_free)) else {
+            unreachable!("checked above that the entry is Used");
         };
 
-        // If it is used, then continue to remove, otherwise do nothing.
-        if let Entry::Used(_) = slab_entry.entry {
-            // If the key's generation does not match, then ignore the removal.
-            if slab_entry.generation != key.generation {
-                return;
-            }
+        // Increment the generation on the slab, invalidating any old SlabKey's referencing it.
+        slab_entry.generation += 1;
 
-            // Increment the genration on the slab, invalidating any old SlabKey's referencing it.
-            slab_entry.generation += 1;
+        // Update next_free to the index of the this entry
+        self.next_free = key.index;
 
-            // Replace the entry with a free entry pointing to next_free
-            slab_entry.entry = Entry::Free(self.next_free);
+        // Decrement the used count
+        self.used -= 1;
 
-            // Update next_free to the index of the this entry
-            self.next_free = key.index;
+        Some(value)
+    }
 
-            // Decrement the used count
-            self.used -= 1;
-        }
-        
+    /// Removes every entry from the slab, returning an iterator over the values that were
+    /// in use. Capacity is not released; call [`Slab::shrink_to_fit`] afterwards if needed.
+    pub fn drain(&mut self) -> impl Iterator<Item = T> + '_ {
+        self.used = 0;
+        self.next_free = 0;
+
+        self.entries.drain(..).filter_map(|slab_entry| match slab_entry.entry {
+            Entry::Used(value) => Some(value),
+            Entry::Free(_) => None,
+        })
+    }
+
+    /// Releases as much unused capacity as possible.
+    pub fn shrink_to_fit(&mut self) {
+        self.entries.shrink_to_fit();
     }
 
 
     /// Reallocates the slab, increasing the capacity by the given amount. If None is given, then the slab's capacity will double.
     pub fn increase_capacity(&mut self, additional: Option<usize>) {
-        // Get the additional capacity, defaulting to doubling the capacity if None is given
-        let additional = additional.unwrap_or(self.entries.capacity());
+        // Get the additional capacity, defaulting to doubling the capacity if None is given.
+        // A zero-capacity slab must still make forward progress, so never grow by less than 1.
+        let additional = additional.unwrap_or(self.entries.capacity()).max(1);
 
         // Increase the capacity
         self.entries.reserve(additional);